@@ -2,8 +2,11 @@
 use std::arch::x86::*;
 #[cfg(target_arch = "x86_64")]
 use std::arch::x86_64::*;
+#[cfg(target_arch = "aarch64")]
+use std::arch::aarch64::*;
 
 use std::alloc;
+use std::collections::VecDeque;
 use std::ffi::OsStr;
 use std::path::Path;
 use thiserror::Error;
@@ -18,6 +21,13 @@ pub fn encode(nuc: &[u8]) -> Vec<u64> {
         }
     }
 
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return unsafe { encode_shift_neon(nuc) };
+        }
+    }
+
     encode_lut(nuc)
 }
 
@@ -93,6 +103,56 @@ unsafe fn encode_movemask_sse(nuc: &[u8]) -> Vec<u64> {
     Vec::from_raw_parts(res_ptr as *mut u64, len, len)
 }
 
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn encode_shift_neon(nuc: &[u8]) -> Vec<u64> {
+    let ptr = nuc.as_ptr();
+    let end_idx = nuc.len() / 16;
+    let len = (nuc.len() / 32) + if nuc.len() % 32 == 0 { 0 } else { 1 };
+
+    // zeroed (not just `alloc`) because when `nuc.len()` is a multiple of 16 but not 32, the
+    // main loop below only ever writes the low `u32` of the last `u64` slot and the tail branch
+    // is skipped, leaving the high `u32` uninitialised otherwise.
+    let layout = alloc::Layout::from_size_align_unchecked(len * 8, 16);
+    let res_ptr = alloc::alloc_zeroed(layout) as *mut u32;
+
+    // shifts the odd nucleotide of each byte pair 2 bits to the left so the pair can be
+    // merged without the bits overlapping
+    let shift_pairs = vld1q_s8([0i8, 2, 0, 2, 0, 2, 0, 2, 0, 2, 0, 2, 0, 2, 0, 2].as_ptr());
+    // shifts the odd nibble of each nibble pair 4 bits to the left, same idea one level up
+    let shift_nibbles = vld1q_s8([0i8, 4, 0, 4, 0, 4, 0, 4, 0, 4, 0, 4, 0, 4, 0, 4].as_ptr());
+
+    for i in 0..end_idx as isize {
+        let v = vld1q_u8(ptr.offset(i * 16));
+
+        // isolate the two code bits (bits 1 and 2 of the ASCII byte) and align them to the
+        // LSB; under the current LUT this recovers the same 2-bit code as `BYTE_LUT` for
+        // every supported nucleotide letter, uppercase or lowercase
+        let code = vshrq_n_u8(vandq_u8(v, vdupq_n_u8(0b0000_0110)), 1);
+
+        // shift every other lane left so adjacent codes occupy disjoint bits, then
+        // pairwise-add (equivalent to OR here, since the bits never overlap) to merge each
+        // pair of nucleotides into a single nibble
+        let positioned = vshlq_u8(code, shift_pairs);
+        let nibbles = vpaddq_u8(positioned, positioned);
+
+        // repeat the same trick one level up to merge nibble pairs into whole bytes
+        let positioned = vshlq_u8(nibbles, shift_nibbles);
+        let bytes = vpaddq_u8(positioned, positioned);
+
+        // the first four packed bytes, read as a little-endian u32, are the 16 codes for
+        // this chunk
+        *res_ptr.offset(i) = vgetq_lane_u32(vreinterpretq_u32_u8(bytes), 0);
+    }
+
+    if nuc.len() % 16 > 0 {
+        *res_ptr.offset(end_idx as isize) =
+            *encode_lut(&nuc[(end_idx * 16)..]).get_unchecked(0) as u32;
+    }
+
+    Vec::from_raw_parts(res_ptr as *mut u64, len, len)
+}
+
 static BYTE_LUT: [u8; 128] = {
     let mut lut = [0u8; 128];
     lut[b'a' as usize] = 0b00;
@@ -141,6 +201,13 @@ pub fn decode(bits: &[u64], len: usize) -> Vec<u8> {
         }
     }
 
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return unsafe { decode_shuffle_neon(bits, len) };
+        }
+    }
+
     decode_lut(bits, len)
 }
 
@@ -232,6 +299,57 @@ unsafe fn decode_shuffle_sse(bits: &[u64], len: usize) -> Vec<u8> {
     Vec::from_raw_parts(ptr as *mut u8, len, bits.len() * 32)
 }
 
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn decode_shuffle_neon(bits: &[u64], len: usize) -> Vec<u8> {
+    let layout = alloc::Layout::from_size_align_unchecked(bits.len() * 32, 16);
+    let ptr = alloc::alloc(layout);
+
+    let bits_ptr = bits.as_ptr() as *const u32;
+
+    // duplicate each byte of the source word four times, one copy per nucleotide it encodes
+    let shuffle_mask = vld1q_u8([0u8, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3].as_ptr());
+    // selects the high (odd) 16-bit lane of every 32-bit pair
+    let odd_lane_mask = vreinterpretq_u16_u32(vdupq_n_u32(0xFFFF_0000));
+    let lo_mask = vdupq_n_u16(0b0000_1100_0000_0011);
+    // index 0..3 looks up a 2-bit code kept in bits 0-1 (the low byte of each 16-bit lane);
+    // index 4, 8, 12 looks up a code kept in bits 2-3 (the high byte), mirroring the x86
+    // SSE/AVX table. Every other index is unused and never produced by `lo_mask` above.
+    let lut = vld1q_u8(
+        [
+            b'A', b'C', b'T', b'G', b'C', 0, 0, 0, b'T', 0, 0, 0, b'G', 0, 0, 0,
+        ]
+        .as_ptr(),
+    );
+
+    for i in 0..(bits.len() * 2) as isize {
+        let word = *bits_ptr.offset(i);
+        let v = vreinterpretq_u8_u32(vdupq_n_u32(word));
+
+        // duplicate each byte four times
+        let v1 = vqtbl1q_u8(v, shuffle_mask);
+
+        // separately right shift each 16-bit chunk by 0 or 4 bits
+        let v2 = vreinterpretq_u8_u16(vshrq_n_u16(vreinterpretq_u16_u8(v1), 4));
+
+        // merge together shifted chunks
+        let v = vreinterpretq_u8_u16(vbslq_u16(
+            odd_lane_mask,
+            vreinterpretq_u16_u8(v2),
+            vreinterpretq_u16_u8(v1),
+        ));
+
+        // only keep two bits in each byte
+        let v = vreinterpretq_u8_u16(vandq_u16(vreinterpretq_u16_u8(v), lo_mask));
+
+        // use lookup table to convert nucleotide bits to bytes
+        let v = vqtbl1q_u8(lut, v);
+        vst1q_u8(ptr.offset(i * 16), v);
+    }
+
+    Vec::from_raw_parts(ptr, len, bits.len() * 32)
+}
+
 static BITS_LUT: [u8; 4] = {
     let mut lut = [0u8; 4];
     lut[0b00] = b'A';
@@ -259,12 +377,158 @@ fn decode_lut(bits: &[u64], len: usize) -> Vec<u8> {
     unsafe { Vec::from_raw_parts(res_ptr, len, len) }
 }
 
+/// Computes the reverse complement of a 2-bit packed k-mer.
+///
+/// Under the current LUT (`A=00, T=10, C=01, G=11`), complementing a base is an XOR with
+/// `0b10`, and reversing the order of bases is the standard bit-reversal trick adapted to
+/// operate on 2-bit groups instead of single bits.
+pub fn revcomp(encoded: u64, k: usize) -> u64 {
+    // complement every base in parallel
+    let mut x = encoded ^ 0xAAAA_AAAA_AAAA_AAAA;
+
+    // reverse the order of all 32 2-bit groups by swapping progressively larger blocks
+    x = ((x & 0x3333_3333_3333_3333) << 2) | ((x >> 2) & 0x3333_3333_3333_3333);
+    x = ((x & 0x0F0F_0F0F_0F0F_0F0F) << 4) | ((x >> 4) & 0x0F0F_0F0F_0F0F_0F0F);
+    x = ((x & 0x00FF_00FF_00FF_00FF) << 8) | ((x >> 8) & 0x00FF_00FF_00FF_00FF);
+    x = ((x & 0x0000_FFFF_0000_FFFF) << 16) | ((x >> 16) & 0x0000_FFFF_0000_FFFF);
+    x = x.rotate_right(32);
+
+    // the k meaningful groups are now at the high end, in reverse order, with any unused
+    // high-order groups of `encoded` pushed down to the low end; shift them out so the
+    // result packs the same way as `encode`
+    x >> (64 - 2 * k)
+}
+
+/// Tracks, for a single k-mer within a single genome, how often it was observed and
+/// (optionally) every position at which it occurred.
+#[derive(Debug, Default, Clone)]
+pub struct KmerInfo {
+    count: usize,
+    /// `(chrom, 0-based position, strand)` for every occurrence of this k-mer, in insertion
+    /// order. Strand is `'+'` unless canonicalisation flipped it. Left empty when position
+    /// tracking is disabled, regardless of `count`.
+    pub positions: Vec<(String, usize, char)>,
+}
+
+impl KmerInfo {
+    /// Records an occurrence of this k-mer at `pos` (0-based) on `chrom`, matched on `strand`.
+    ///
+    /// The occurrence always counts towards [`KmerInfo::count`]; it is only appended to
+    /// [`KmerInfo::positions`] when `track_positions` is set, so callers can bound memory use
+    /// on large inputs by counting without remembering where each k-mer was seen.
+    pub fn add_pos(&mut self, chrom: &str, pos: usize, strand: char, track_positions: bool) {
+        self.count += 1;
+        if track_positions {
+            self.positions.push((chrom.to_string(), pos, strand));
+        }
+    }
+
+    /// The number of times this k-mer was observed.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+/// How widely a k-mer is shared across the genomes scanned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KmerClass {
+    /// Present in every genome scanned.
+    Core,
+    /// Present in more than one genome, but not all of them.
+    Accessory,
+    /// Present in exactly one genome.
+    Unique,
+}
+
+/// The number of genomes (out of `infos`, one [`KmerInfo`] per genome) a k-mer was observed in
+/// at least once.
+pub fn n_genomes_present(infos: &[KmerInfo]) -> usize {
+    infos.iter().filter(|info| info.count() > 0).count()
+}
+
+/// Classifies a k-mer as [`KmerClass::Core`], [`KmerClass::Accessory`], or [`KmerClass::Unique`]
+/// given how many of the `n_genomes` scanned genomes it was present in.
+pub fn classify_kmer(n_present: usize, n_genomes: usize) -> KmerClass {
+    if n_present == n_genomes {
+        KmerClass::Core
+    } else if n_present == 1 {
+        KmerClass::Unique
+    } else {
+        KmerClass::Accessory
+    }
+}
+
+/// A cheap, invertible hash used to rank k-mers when selecting minimizers.
+///
+/// Using the raw 2-bit encoding directly would bias selection toward poly-A runs (which encode
+/// to all-zero bits), so every k-mer is mixed with a multiply-xor-shift first.
+pub fn minimizer_hash(encoded: u64) -> u64 {
+    let mut h = encoded;
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^= h >> 33;
+    h
+}
+
+/// Selects minimizers from a stream of k-mers using a monotonic deque over a sliding window of
+/// `window` consecutive k-mers.
+///
+/// Call [`MinimizerWindow::push`] once per k-mer, in position order; it returns the position and
+/// payload of the new minimizer whenever the window slides onto one that hasn't already been emitted (the
+/// same minimizer commonly persists across several adjacent windows).
+#[derive(Debug)]
+pub struct MinimizerWindow<T> {
+    window: usize,
+    deque: VecDeque<(usize, T, u64)>,
+    last_emitted: Option<usize>,
+}
+
+impl<T: Copy> MinimizerWindow<T> {
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            deque: VecDeque::new(),
+            last_emitted: None,
+        }
+    }
+
+    /// Feeds the k-mer at `pos` (0-based, strictly increasing between calls) into the window,
+    /// ranked by `hash` and carrying `payload` for later retrieval.
+    pub fn push(&mut self, pos: usize, payload: T, hash: u64) -> Option<(usize, T)> {
+        while matches!(self.deque.back(), Some((_, _, h)) if *h >= hash) {
+            self.deque.pop_back();
+        }
+        self.deque.push_back((pos, payload, hash));
+
+        while matches!(self.deque.front(), Some((p, _, _)) if pos - p >= self.window) {
+            self.deque.pop_front();
+        }
+
+        if pos + 1 < self.window {
+            return None;
+        }
+
+        let &(min_pos, min_payload, _) = self.deque.front().unwrap();
+        if self.last_emitted == Some(min_pos) {
+            None
+        } else {
+            self.last_emitted = Some(min_pos);
+            Some((min_pos, min_payload))
+        }
+    }
+}
+
 /// A collection of custom errors relating to the command line interface for this package.
 #[derive(Error, Debug, PartialEq)]
 pub enum CliError {
     /// Indicates that a string cannot be parsed into a [`CompressionFormat`](#compressionformat).
     #[error("{0} is not a valid output format")]
     InvalidCompression(String),
+    /// Indicates that a string cannot be parsed into an [`OutputFormat`].
+    #[error("{0} is not a valid record format")]
+    InvalidOutputFormat(String),
 }
 
 pub trait CompressionExt {
@@ -297,6 +561,99 @@ pub fn parse_compression_format(s: &str) -> Result<niffler::compression::Format,
     }
 }
 
+/// The format in which shared k-mers are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// One FASTA record per shared k-mer.
+    Fasta,
+    /// Maximal exact-match blocks, one BED interval per merged run of shared k-mers.
+    Bed,
+    /// A k-mer-by-genome presence/count matrix, one row per k-mer.
+    Tsv,
+}
+
+impl OutputFormat {
+    /// Infers the output format from a file path, looking past any compression extension.
+    ///
+    /// Recognises `fasta`/`fa` and `bed` explicitly; any other (or missing) extension falls back
+    /// to [`OutputFormat::Tsv`], the tool's default output.
+    pub fn from_path<S: AsRef<OsStr> + ?Sized>(p: &S) -> Self {
+        let path = Path::new(p);
+        let decompressed = match path.extension().map(|s| s.to_str()) {
+            Some(Some("gz" | "zst" | "bz" | "bz2" | "lzma")) => path.file_stem().map(Path::new),
+            _ => Some(path),
+        };
+        match decompressed.and_then(|p| p.extension()).map(|s| s.to_str()) {
+            Some(Some("bed")) => Self::Bed,
+            Some(Some("fasta" | "fa")) => Self::Fasta,
+            _ => Self::Tsv,
+        }
+    }
+}
+
+pub fn parse_output_format(s: &str) -> Result<OutputFormat, CliError> {
+    match s.to_lowercase().as_str() {
+        "fasta" | "fa" => Ok(OutputFormat::Fasta),
+        "bed" => Ok(OutputFormat::Bed),
+        "tsv" => Ok(OutputFormat::Tsv),
+        _ => Err(CliError::InvalidOutputFormat(s.to_string())),
+    }
+}
+
+/// A single maximal exact-match block: a run of shared k-mers at consecutive positions on the
+/// same chromosome, ready to be written as one BED line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BedInterval {
+    pub chrom: String,
+    /// 0-based, inclusive start.
+    pub start: usize,
+    /// 0-based, exclusive end.
+    pub end: usize,
+    /// Number of shared k-mers merged into this block.
+    pub n_kmers: usize,
+}
+
+/// Collapses runs of consecutive k-mer occurrences into maximal exact-match blocks.
+///
+/// `positions` need not be sorted or grouped by chromosome. `k` is the k-mer size, used to turn
+/// the span of `n` merged k-mer start positions into a block of `n + k - 1` bases. A run ends as
+/// soon as the next position on the same chromosome is not exactly one greater than the previous.
+pub fn merge_intervals(positions: &[(String, usize, char)], k: usize) -> Vec<BedInterval> {
+    let mut sorted: Vec<(&str, usize)> =
+        positions.iter().map(|(c, p, _)| (c.as_str(), *p)).collect();
+    sorted.sort_unstable();
+
+    let mut blocks = Vec::new();
+    let mut iter = sorted.into_iter();
+    let Some((chrom, pos)) = iter.next() else {
+        return blocks;
+    };
+
+    let (mut block_chrom, mut block_start, mut prev_pos, mut n) = (chrom, pos, pos, 1usize);
+    for (chrom, pos) in iter {
+        if chrom == block_chrom && pos == prev_pos + 1 {
+            prev_pos = pos;
+            n += 1;
+        } else {
+            blocks.push(BedInterval {
+                chrom: block_chrom.to_string(),
+                start: block_start,
+                end: prev_pos + k,
+                n_kmers: n,
+            });
+            (block_chrom, block_start, prev_pos, n) = (chrom, pos, pos, 1);
+        }
+    }
+    blocks.push(BedInterval {
+        chrom: block_chrom.to_string(),
+        start: block_start,
+        end: prev_pos + k,
+        n_kmers: n,
+    });
+
+    blocks
+}
+
 /// A utility function to validate compression level is in allowed range
 #[allow(clippy::redundant_clone)]
 pub fn parse_level(s: &str) -> Result<niffler::Level, String> {
@@ -326,3 +683,247 @@ pub fn parse_level(s: &str) -> Result<niffler::Level, String> {
     };
     Ok(lvl)
 }
+
+#[cfg(all(test, target_arch = "aarch64"))]
+mod neon_tests {
+    use super::*;
+
+    const SEQ: &[u8] = b"ACGTTGCAACGTTGCAACGTTGCAACGTTGCAACGTTGCA";
+
+    #[test]
+    fn encode_neon_matches_scalar_for_all_k() {
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            return;
+        }
+        for k in 1..=32 {
+            let nuc = &SEQ[..k];
+            let scalar = encode_lut(nuc);
+            let neon = unsafe { encode_shift_neon(nuc) };
+            assert_eq!(scalar, neon, "encode mismatch at k={}", k);
+        }
+    }
+
+    #[test]
+    fn decode_neon_matches_scalar_for_all_k() {
+        if !std::arch::is_aarch64_feature_detected!("neon") {
+            return;
+        }
+        for k in 1..=32 {
+            let nuc = &SEQ[..k];
+            let bits = encode_lut(nuc);
+            let scalar = decode_lut(&bits, k);
+            let neon = unsafe { decode_shuffle_neon(&bits, k) };
+            assert_eq!(scalar, neon, "decode mismatch at k={}", k);
+        }
+    }
+}
+
+#[cfg(test)]
+mod revcomp_tests {
+    use super::*;
+
+    fn complement(b: u8) -> u8 {
+        match b {
+            b'A' => b'T',
+            b'T' => b'A',
+            b'C' => b'G',
+            b'G' => b'C',
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn matches_naive_reverse_complement() {
+        let seq = b"ACGTTGCAACGT";
+        let k = seq.len();
+        let encoded = encode_lut(seq)[0];
+
+        let expected: Vec<u8> = seq.iter().rev().map(|&b| complement(b)).collect();
+        let expected_encoded = encode_lut(&expected)[0];
+
+        assert_eq!(revcomp(encoded, k), expected_encoded);
+    }
+
+    #[test]
+    fn is_involutive() {
+        let seq = b"ACGTTGCAACGT";
+        let k = seq.len();
+        let encoded = encode_lut(seq)[0];
+
+        assert_eq!(revcomp(revcomp(encoded, k), k), encoded);
+    }
+
+    #[test]
+    fn palindrome_is_its_own_reverse_complement() {
+        let seq = b"ACGT";
+        let k = seq.len();
+        let encoded = encode_lut(seq)[0];
+
+        assert_eq!(revcomp(encoded, k), encoded);
+    }
+}
+
+#[cfg(test)]
+mod minimizer_window_tests {
+    use super::*;
+
+    #[test]
+    fn emits_the_running_minimum_as_the_window_slides() {
+        let mut win = MinimizerWindow::new(3);
+
+        assert_eq!(win.push(0, 'a', 5), None);
+        assert_eq!(win.push(1, 'b', 2), None);
+        // window is full; (1, 'b') is the minimum so far
+        assert_eq!(win.push(2, 'c', 8), Some((1, 'b')));
+        // (1, 'b') is still in the window and still the minimum, so it isn't re-emitted
+        assert_eq!(win.push(3, 'd', 9), None);
+        // (1, 'b') falls out of the window and a new, lower-hashed minimum takes over
+        assert_eq!(win.push(4, 'e', 1), Some((4, 'e')));
+    }
+
+    #[test]
+    fn window_of_one_emits_every_position() {
+        let mut win = MinimizerWindow::new(1);
+
+        assert_eq!(win.push(0, 'a', 10), Some((0, 'a')));
+        assert_eq!(win.push(1, 'b', 20), Some((1, 'b')));
+        assert_eq!(win.push(2, 'c', 5), Some((2, 'c')));
+    }
+}
+
+#[cfg(test)]
+mod merge_intervals_tests {
+    use super::*;
+
+    #[test]
+    fn merges_consecutive_positions_on_the_same_chrom() {
+        let positions = vec![
+            ("chr1".to_string(), 10, '+'),
+            ("chr1".to_string(), 11, '+'),
+            ("chr1".to_string(), 12, '+'),
+        ];
+
+        let blocks = merge_intervals(&positions, 5);
+
+        assert_eq!(
+            blocks,
+            vec![BedInterval {
+                chrom: "chr1".to_string(),
+                start: 10,
+                end: 17,
+                n_kmers: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn breaks_a_run_on_a_gap() {
+        let positions = vec![
+            ("chr1".to_string(), 10, '+'),
+            ("chr1".to_string(), 11, '+'),
+            ("chr1".to_string(), 20, '+'),
+        ];
+
+        let blocks = merge_intervals(&positions, 4);
+
+        assert_eq!(
+            blocks,
+            vec![
+                BedInterval {
+                    chrom: "chr1".to_string(),
+                    start: 10,
+                    end: 15,
+                    n_kmers: 2,
+                },
+                BedInterval {
+                    chrom: "chr1".to_string(),
+                    start: 20,
+                    end: 24,
+                    n_kmers: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_merge_across_chromosomes() {
+        let positions = vec![("chr2".to_string(), 0, '+'), ("chr1".to_string(), 0, '+')];
+
+        let blocks = merge_intervals(&positions, 3);
+
+        assert_eq!(
+            blocks,
+            vec![
+                BedInterval {
+                    chrom: "chr1".to_string(),
+                    start: 0,
+                    end: 3,
+                    n_kmers: 1,
+                },
+                BedInterval {
+                    chrom: "chr2".to_string(),
+                    start: 0,
+                    end: 3,
+                    n_kmers: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_input_yields_no_blocks() {
+        assert_eq!(merge_intervals(&[], 5), Vec::new());
+    }
+}
+
+#[cfg(test)]
+mod kmer_class_tests {
+    use super::*;
+
+    fn present(n: usize) -> KmerInfo {
+        let mut info = KmerInfo::default();
+        for i in 0..n {
+            info.add_pos("chr1", i, '+', false);
+        }
+        info
+    }
+    fn absent() -> KmerInfo {
+        KmerInfo::default()
+    }
+
+    #[test]
+    fn present_in_every_genome_is_core() {
+        let infos = vec![present(1), present(2), present(1)];
+        let n_present = n_genomes_present(&infos);
+
+        assert_eq!(n_present, 3);
+        assert_eq!(classify_kmer(n_present, infos.len()), KmerClass::Core);
+    }
+
+    #[test]
+    fn present_in_exactly_one_genome_is_unique() {
+        let infos = vec![present(1), absent(), absent()];
+        let n_present = n_genomes_present(&infos);
+
+        assert_eq!(n_present, 1);
+        assert_eq!(classify_kmer(n_present, infos.len()), KmerClass::Unique);
+    }
+
+    #[test]
+    fn present_in_some_but_not_all_genomes_is_accessory() {
+        let infos = vec![present(1), present(1), absent()];
+        let n_present = n_genomes_present(&infos);
+
+        assert_eq!(n_present, 2);
+        assert_eq!(classify_kmer(n_present, infos.len()), KmerClass::Accessory);
+    }
+
+    #[test]
+    fn min_genomes_filters_out_kmers_below_the_threshold() {
+        let below_threshold = vec![present(1), absent(), absent()];
+        let at_threshold = vec![present(1), present(1), absent()];
+
+        assert!(n_genomes_present(&below_threshold) < 2);
+        assert!(n_genomes_present(&at_threshold) >= 2);
+    }
+}
@@ -2,34 +2,66 @@ use anyhow::{Context, Result};
 use clap::Parser;
 use env_logger::Builder;
 use itertools::Itertools;
-use log::{info, LevelFilter};
+use log::{info, warn, LevelFilter};
 use noodles::fasta;
 use noodles::fasta::record::{Definition, Sequence};
 use skc::*;
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{stdout, BufReader, BufWriter};
+use std::io::{stdout, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Target sequence (smallest of the two genomes recommended)
+    /// Genomes to compare (2 or more); each becomes one column of the output k-mer matrix
     ///
-    /// Can be compressed with gzip, bzip2, xz, or zstd
-    #[arg()]
-    target: String,
-    /// Query sequence
-    ///
-    /// Can be compressed with gzip, bzip2, xz, or zstd
-    #[arg()]
-    query: String,
+    /// Can be compressed with gzip, bzip2, xz, or zstd.
+    #[arg(required = true, num_args = 2..)]
+    genomes: Vec<String>,
     /// Size of k-mers (max. 32)
     #[arg(short, long, default_value_t = 21, value_parser = clap::value_parser!(u64).range(1..=32))]
     kmer: u64,
+    /// Canonicalise k-mers (strand-insensitive matching)
+    ///
+    /// Each k-mer is compared to its reverse complement and the lexicographically smaller of
+    /// the two (by bit pattern) is used for hashing, so a k-mer on the forward strand of one
+    /// genome can match its reverse-complement counterpart on the other.
+    #[arg(short, long)]
+    canonical: bool,
+    /// Only index one k-mer per window of W consecutive k-mers
+    ///
+    /// Instead of storing every k-mer, only the minimizer (the k-mer with the smallest hash) of
+    /// each sliding window of W consecutive k-mers is kept. This reduces the number of k-mers
+    /// stored by roughly a factor of 2/(W+1), trading some sensitivity for lower memory use on
+    /// large genomes.
+    #[arg(short = 'w', long, value_parser = clap::value_parser!(u64).range(1..))]
+    window: Option<u64>,
+    /// Only report k-mers present in at least N genomes
+    ///
+    /// 1 (the default) keeps every k-mer seen anywhere; setting this to the number of genomes
+    /// given keeps only core k-mers (present in all of them).
+    #[arg(short = 'g', long, default_value_t = 1)]
+    min_genomes: usize,
+    /// Track the position of every k-mer occurrence within each genome
+    ///
+    /// Off by default to bound memory when scanning many large genomes, since the `tsv` matrix
+    /// output only needs per-genome counts. Positions are always tracked for `bed` output,
+    /// regardless of this flag, since merging exact-match blocks requires them.
+    #[arg(short, long)]
+    positions: bool,
     /// Output filepath(s); stdout if not present.
     #[clap(short, long)]
     pub output: Option<PathBuf>,
+    /// fasta: one record per k-mer; bed: maximal exact-match blocks; tsv: k-mer-by-genome count
+    /// matrix (the default)
+    ///
+    /// Output record format is automatically guessed from the output filename extension
+    /// (`.fasta`/`.fa` or `.bed`, optionally followed by a compression extension); any other
+    /// extension, or no `--output` path at all, defaults to `tsv`. This option is used to
+    /// override that.
+    #[clap(short, long, value_name = "fasta|bed|tsv", value_parser = parse_output_format)]
+    pub format: Option<OutputFormat>,
     /// u: uncompressed; b: Bzip2; g: Gzip; l: Lzma; z: Zstd
     ///
     /// Output compression format is automatically guessed from the filename extension. This option
@@ -40,6 +72,26 @@ struct Args {
     #[clap(short = 'l', long, value_parser = parse_level, default_value="6", value_name = "INT")]
     pub compress_level: niffler::Level,
 }
+/// Returns the hash to use for a k-mer and the strand it was matched on.
+///
+/// When `canonical` is disabled, the k-mer is used as-is and always reported as `'+'`.
+/// Otherwise the k-mer is compared against its reverse complement and the smaller of the two
+/// (by bit pattern) is used, so homologous regions on opposite strands hash identically. A
+/// k-mer that is its own reverse complement is always `'+'` and is therefore only ever counted
+/// once.
+fn canonicalise(kmer: u64, k: usize, canonical: bool) -> (u64, char) {
+    if !canonical {
+        return (kmer, '+');
+    }
+
+    let rc = revcomp(kmer, k);
+    if rc < kmer {
+        (rc, '-')
+    } else {
+        (kmer, '+')
+    }
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
 
@@ -51,31 +103,22 @@ fn main() -> Result<()> {
         .init();
 
     let k = args.kmer as usize;
+    let n_genomes = args.genomes.len();
 
-    let (reader, _compression) = niffler::from_path(Path::new(&args.target))
-        .with_context(|| format!("Failed to open {}", args.target))?;
-    let mut fa_reader = fasta::Reader::new(BufReader::new(reader));
-
-    let mut target_kmers: HashMap<u64, KmerInfo> = HashMap::new();
-
-    for (n_rec, record) in fa_reader.records().enumerate() {
-        let record = record.with_context(|| {
-            format!("Failed to parse record {} (zero-based) from target", n_rec)
-        })?;
-        let chrom = record.name();
-        let seq = record.sequence().as_ref();
-        for i in 0..seq.len() {
-            let Some(kmer) = &seq.get(i..i + k) else {continue};
-            let h = encode(kmer)[0];
-            target_kmers.entry(h).or_default().add_pos(chrom, i);
-        }
+    if args.min_genomes > n_genomes {
+        warn!(
+            "--min-genomes {} is greater than the {} genomes given; no k-mers will pass",
+            args.min_genomes, n_genomes
+        );
     }
 
-    info!("{} unique k-mers in target", target_kmers.len());
-
-    let (reader, _compression) = niffler::from_path(Path::new(&args.query))
-        .with_context(|| format!("Failed to open {}", args.query))?;
-    let mut fa_reader = fasta::Reader::new(BufReader::new(reader));
+    let format = args.format.unwrap_or_else(|| match &args.output {
+        Some(p) => OutputFormat::from_path(p),
+        None => OutputFormat::Tsv,
+    });
+    // bed output merges runs of consecutive positions into exact-match blocks, so it always
+    // needs positions, whether or not the user asked for them.
+    let track_positions = args.positions || format == OutputFormat::Bed;
 
     let output_handle = match &args.output {
         None => match args.output_type {
@@ -100,50 +143,161 @@ fn main() -> Result<()> {
         }
     };
 
-    let mut fa_writer = fasta::Writer::new(output_handle);
-
-    let mut query_kmers: HashMap<u64, KmerInfo> = HashMap::new();
-
-    for (n_rec, record) in fa_reader.records().enumerate() {
-        let record = record.context(format!(
-            "Failed to parse record {} (zero-based) from query",
-            n_rec
-        ))?;
-        let chrom = record.name();
-        let seq = record.sequence().as_ref();
-        for i in 0..seq.len() {
-            let Some(kmer) = &seq.get(i..i + k) else { continue };
-            let h = encode(kmer)[0];
-            if target_kmers.contains_key(&h) {
-                query_kmers.entry(h).or_default().add_pos(chrom, i);
+    let mut kmer_table: HashMap<u64, Vec<KmerInfo>> = HashMap::new();
+
+    for (gi, path) in args.genomes.iter().enumerate() {
+        let (reader, _compression) = niffler::from_path(Path::new(path))
+            .with_context(|| format!("Failed to open {}", path))?;
+        let mut fa_reader = fasta::Reader::new(BufReader::new(reader));
+
+        for (n_rec, record) in fa_reader.records().enumerate() {
+            let record = record.with_context(|| {
+                format!(
+                    "Failed to parse record {} (zero-based) from {}",
+                    n_rec, path
+                )
+            })?;
+            let chrom = record.name();
+            let seq = record.sequence().as_ref();
+            let mut minimizer_window = args.window.map(|w| MinimizerWindow::new(w as usize));
+            for i in 0..seq.len() {
+                let Some(kmer) = &seq.get(i..i + k) else {
+                    continue;
+                };
+                let (h, strand) = canonicalise(encode(kmer)[0], k, args.canonical);
+                match &mut minimizer_window {
+                    Some(win) => {
+                        if let Some((pos, (h, strand))) =
+                            win.push(i, (h, strand), minimizer_hash(h))
+                        {
+                            kmer_table
+                                .entry(h)
+                                .or_insert_with(|| vec![KmerInfo::default(); n_genomes])[gi]
+                                .add_pos(chrom, pos, strand, track_positions);
+                        }
+                    }
+                    None => {
+                        kmer_table
+                            .entry(h)
+                            .or_insert_with(|| vec![KmerInfo::default(); n_genomes])[gi]
+                            .add_pos(chrom, i, strand, track_positions);
+                    }
+                }
             }
         }
+
+        info!(
+            "Indexed {} ({} unique k-mers seen so far)",
+            path,
+            kmer_table.len()
+        );
+    }
+
+    let mut core = 0usize;
+    let mut unique = 0usize;
+    for infos in kmer_table.values() {
+        match classify_kmer(n_genomes_present(infos), n_genomes) {
+            KmerClass::Core => core += 1,
+            KmerClass::Unique => unique += 1,
+            KmerClass::Accessory => {}
+        }
     }
     info!(
-        "{} shared k-mers between target and query",
-        query_kmers.len()
+        "{} total k-mers: {} core (in all {} genomes), {} unique (in exactly 1), {} accessory",
+        kmer_table.len(),
+        core,
+        n_genomes,
+        unique,
+        kmer_table.len() - core - unique
     );
 
-    for (h, query_kmerinfo) in query_kmers {
-        let kmer = decode(&[h], k);
-        // safe to unwrap as we know the hash is in target
-        let target_kmerinfo = target_kmers.get(&h).unwrap();
-        let mut description = format!(
-            "tcount={} qcount={} ",
-            target_kmerinfo.count(),
-            query_kmerinfo.count()
-        );
-        let target_positions = target_kmerinfo.positions.iter().join(",");
-        let query_positions = query_kmerinfo.positions.iter().join(",");
-        let pos_descr = format!("tpos={} qpos={}", target_positions, query_positions);
-        description.push_str(&pos_descr);
-        let definition = Definition::new(h.to_string(), Some(description));
-        let seq = Sequence::from(kmer);
-        let record = fasta::Record::new(definition, seq);
-
-        fa_writer
-            .write_record(&record)
-            .context("Failed to write record to output")?;
+    match format {
+        OutputFormat::Tsv => {
+            let mut output_handle = output_handle;
+            write!(output_handle, "kmer").context("Failed to write TSV header")?;
+            for genome in &args.genomes {
+                write!(output_handle, "\t{}", genome).context("Failed to write TSV header")?;
+            }
+            writeln!(output_handle).context("Failed to write TSV header")?;
+
+            for (h, infos) in &kmer_table {
+                if n_genomes_present(infos) < args.min_genomes {
+                    continue;
+                }
+                let kmer = decode(&[*h], k);
+                let kmer = String::from_utf8(kmer).expect("decoded k-mer is always valid UTF-8");
+                write!(output_handle, "{}", kmer).context("Failed to write TSV row")?;
+                for info in infos {
+                    write!(output_handle, "\t{}", info.count())
+                        .context("Failed to write TSV row")?;
+                }
+                writeln!(output_handle).context("Failed to write TSV row")?;
+            }
+        }
+        OutputFormat::Fasta => {
+            let mut fa_writer = fasta::Writer::new(output_handle);
+
+            for (h, infos) in &kmer_table {
+                let n_present = n_genomes_present(infos);
+                if n_present < args.min_genomes {
+                    continue;
+                }
+                let kmer = decode(&[*h], k);
+                let counts = infos.iter().map(|i| i.count()).join(",");
+                let mut description = format!("n_genomes={} counts={}", n_present, counts);
+                if track_positions {
+                    let positions = infos
+                        .iter()
+                        .enumerate()
+                        .map(|(gi, info)| {
+                            let p = info
+                                .positions
+                                .iter()
+                                .map(|(chrom, pos, strand)| format!("{}:{}{}", chrom, pos, strand))
+                                .join(",");
+                            format!("g{}={}", gi, p)
+                        })
+                        .join(" ");
+                    description.push_str(&format!(" pos={}", positions));
+                }
+                let definition = Definition::new(h.to_string(), Some(description));
+                let seq = Sequence::from(kmer);
+                let record = fasta::Record::new(definition, seq);
+
+                fa_writer
+                    .write_record(&record)
+                    .context("Failed to write record to output")?;
+            }
+        }
+        OutputFormat::Bed => {
+            let mut output_handle = output_handle;
+
+            let passing: Vec<&Vec<KmerInfo>> = kmer_table
+                .values()
+                .filter(|infos| n_genomes_present(infos) >= args.min_genomes)
+                .collect();
+
+            for gi in 0..n_genomes {
+                let positions: Vec<(String, usize, char)> = passing
+                    .iter()
+                    .flat_map(|infos| infos[gi].positions.iter().cloned())
+                    .collect();
+
+                for block in merge_intervals(&positions, k) {
+                    writeln!(
+                        output_handle,
+                        "{}\t{}\t{}\t{}\t{}\t{}",
+                        block.chrom,
+                        block.start,
+                        block.end,
+                        args.genomes[gi],
+                        block.n_kmers,
+                        block.end - block.start
+                    )
+                    .context("Failed to write BED record to output")?;
+                }
+            }
+        }
     }
 
     Ok(())